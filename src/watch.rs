@@ -1,16 +1,31 @@
-use futures::{Async, Future, Poll};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::stream::FuturesUnordered;
+use futures::task::{self, Task};
+use futures::{Async, Future, Poll, Stream};
+use log::warn;
 
 use actor::{Actor, AsyncContext};
 use address::{channel, Addr, Recipient, RecipientRequest};
 use arbiter::Arbiter;
 use context::Context;
 use contextimpl::ContextFut;
-use handler::Message;
+use fut::{self, ActorFuture, WrapFuture};
+use handler::{Handler, Message};
 use mailbox::DEFAULT_CAPACITY;
 use msgs::Execute;
 
-#[derive(Debug,Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum WatchEvent {
+    Starting,
+    Running,
+    Restarting { attempt: usize },
+    Restarted,
+    Stopping,
     Stopped,
 }
 
@@ -18,126 +33,940 @@ impl Message for WatchEvent {
     type Result = ();
 }
 
+struct WatchInner {
+    state: Mutex<WatchEvent>,
+    version: AtomicUsize,
+    next_waiter_id: AtomicUsize,
+    /// One waker slot per live `WatchHandle`, keyed by its `waiter_id`. A
+    /// single shared slot would only ever remember the most recently polled
+    /// clone, silently starving every other one; registering per-handle
+    /// means every independently polled clone is woken on each transition.
+    wakers: Mutex<HashMap<usize, Task>>,
+}
+
+/// Sending half of a `WatchHandle` channel, held by the `Watcher` to publish
+/// its current lifecycle state.
+struct WatchSender {
+    inner: Arc<WatchInner>,
+}
+
+impl WatchSender {
+    fn set(&self, event: WatchEvent) {
+        *self.inner.state.lock().unwrap() = event;
+        self.inner.version.fetch_add(1, Ordering::SeqCst);
+        for waker in self.inner.wakers.lock().unwrap().values() {
+            waker.notify();
+        }
+    }
+
+    /// Create a new handle observing this channel, seeded with the current
+    /// state so it won't spuriously resolve on its first poll.
+    fn subscribe(&self) -> WatchHandle {
+        WatchHandle {
+            inner: self.inner.clone(),
+            seen: self.inner.version.load(Ordering::SeqCst),
+            waiter_id: self.inner.next_waiter_id.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+}
+
+/// Always holds the most recently published `WatchEvent` for a `Watcher`.
+/// `borrow` returns the current state immediately, even if the transition
+/// already happened before this handle existed; polling it as a `Future`
+/// resolves only once the state changes again, coalescing any transitions
+/// that happened in between into the latest value.
+pub struct WatchHandle {
+    inner: Arc<WatchInner>,
+    seen: usize,
+    waiter_id: usize,
+}
+
+impl WatchHandle {
+    fn channel(initial: WatchEvent) -> (WatchSender, WatchHandle) {
+        let inner = Arc::new(WatchInner {
+            state: Mutex::new(initial),
+            version: AtomicUsize::new(0),
+            next_waiter_id: AtomicUsize::new(1),
+            wakers: Mutex::new(HashMap::new()),
+        });
+
+        let handle = WatchHandle {
+            inner: inner.clone(),
+            seen: 0,
+            waiter_id: 0,
+        };
+
+        (WatchSender { inner }, handle)
+    }
+
+    pub fn borrow(&self) -> WatchEvent {
+        self.inner.state.lock().unwrap().clone()
+    }
+}
+
+impl Clone for WatchHandle {
+    fn clone(&self) -> Self {
+        WatchHandle {
+            inner: self.inner.clone(),
+            seen: self.inner.version.load(Ordering::SeqCst),
+            waiter_id: self.inner.next_waiter_id.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.inner.wakers.lock().unwrap().remove(&self.waiter_id);
+    }
+}
+
+impl Future for WatchHandle {
+    type Item = WatchEvent;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<WatchEvent, ()> {
+        self.inner
+            .wakers
+            .lock()
+            .unwrap()
+            .insert(self.waiter_id, task::current());
+
+        let version = self.inner.version.load(Ordering::SeqCst);
+        if version != self.seen {
+            self.seen = version;
+            Ok(Async::Ready(self.borrow()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+/// Register interest in a running `Watcher`'s lifecycle events.
+/// Resolves to an id that can later be passed to `Unsubscribe`.
+pub struct Subscribe(pub Recipient<WatchEvent>);
+
+impl Message for Subscribe {
+    type Result = usize;
+}
+
+/// Drop interest previously registered via `Subscribe`.
+pub struct Unsubscribe(pub usize);
+
+impl Message for Unsubscribe {
+    type Result = ();
+}
+
+/// Controls whether a supervised actor is restarted after it stops.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestartPolicy {
+    /// Never restart; the first stop is final.
+    Never,
+    /// Always restart, no matter how the actor stopped.
+    Always,
+    /// Restart only when the watched future resolved with an error.
+    OnPanic,
+    /// Restart up to `n` times, then give up and stay stopped.
+    UpTo(usize),
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy::Never
+    }
+}
+
+/// Exponential backoff schedule applied between restart attempts.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    base: Duration,
+    multiplier: f64,
+    max: Duration,
+}
+
+impl Backoff {
+    pub fn new(base: Duration, multiplier: f64, max: Duration) -> Backoff {
+        Backoff {
+            base,
+            multiplier,
+            max,
+        }
+    }
+
+    fn delay_for(&self, attempt: usize) -> Duration {
+        let scaled = self.base.as_millis() as f64 * self.multiplier.powi(attempt as i32);
+        let capped = scaled.min(self.max.as_millis() as f64);
+        Duration::from_millis(capped as u64)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Backoff {
+        Backoff::new(Duration::from_millis(100), 2.0, Duration::from_secs(30))
+    }
+}
+
+/// What to do with a notification for a subscriber that already has
+/// `BufferPolicy::max_in_flight` deliveries outstanding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverflowPolicy {
+    /// Replace whatever is queued behind the in-flight deliveries with the
+    /// newest event, discarding the one it replaces.
+    DropOldest,
+    /// Keep whatever is already queued and discard the new event.
+    DropNewest,
+    /// Queue the newest event as `DropOldest` does, but report backpressure
+    /// until the backlog drains: `Watcher` holds back a pending restart and
+    /// the stream bridge stops pulling items. The watched actor itself keeps
+    /// running.
+    Block,
+}
+
+impl Default for OverflowPolicy {
+    fn default() -> Self {
+        OverflowPolicy::DropOldest
+    }
+}
+
+/// Caps how many deliveries may be in flight for a single subscriber at
+/// once, and what happens once that cap is hit.
+#[derive(Debug, Clone, Copy)]
+pub struct BufferPolicy {
+    pub max_in_flight: usize,
+    pub overflow: OverflowPolicy,
+}
+
+impl BufferPolicy {
+    pub fn new(max_in_flight: usize, overflow: OverflowPolicy) -> BufferPolicy {
+        BufferPolicy {
+            max_in_flight,
+            overflow,
+        }
+    }
+}
+
+impl Default for BufferPolicy {
+    fn default() -> BufferPolicy {
+        BufferPolicy::new(1, OverflowPolicy::DropOldest)
+    }
+}
+
+/// Wraps a subscriber's `send` future so the `FuturesUnordered` set can carry
+/// the subscriber id alongside the delivery outcome without ever failing
+/// itself (a closed mailbox is reported as `delivered == false`, not an
+/// error, so one dead subscriber can't stall the rest of the set).
+type Delivery = Box<dyn Future<Item = (usize, bool), Error = ()>>;
+
+fn track_delivery<M>(id: usize, request: RecipientRequest<M>) -> Delivery
+where
+    M: Message<Result = ()>,
+{
+    Box::new(request.then(move |res| Ok((id, res.is_ok()))))
+}
+
+/// Subscriber bookkeeping shared by anything that fans `WatchEvent`s out to a
+/// set of `Recipient`s with bounded per-subscriber buffering: caps in-flight
+/// deliveries per subscriber per `BufferPolicy`, coalesces repeats behind a
+/// full buffer, drains completions concurrently via `FuturesUnordered`, and
+/// prunes subscribers whose mailbox has closed. `Watcher` and the stream
+/// bridge (`watch_stream`) both hold one rather than duplicating this logic.
+struct NotificationHub {
+    subscribers: Vec<(usize, Recipient<WatchEvent>)>,
+    next_subscriber_id: usize,
+    notifications: FuturesUnordered<Delivery>,
+    in_flight: HashMap<usize, usize>,
+    pending: HashMap<usize, WatchEvent>,
+    buffer: BufferPolicy,
+    failed_deliveries: usize,
+}
+
+impl NotificationHub {
+    fn new(buffer: BufferPolicy) -> NotificationHub {
+        NotificationHub {
+            subscribers: Vec::new(),
+            next_subscriber_id: 0,
+            notifications: FuturesUnordered::new(),
+            in_flight: HashMap::new(),
+            pending: HashMap::new(),
+            buffer,
+            failed_deliveries: 0,
+        }
+    }
+
+    fn subscribe(&mut self, recipient: Recipient<WatchEvent>) -> usize {
+        let id = self.next_subscriber_id;
+        self.next_subscriber_id += 1;
+        self.subscribers.push((id, recipient));
+        id
+    }
+
+    fn unsubscribe(&mut self, id: usize) {
+        self.subscribers.retain(|(sid, _)| *sid != id);
+        self.in_flight.remove(&id);
+        self.pending.remove(&id);
+    }
+
+    fn failed_deliveries(&self) -> usize {
+        self.failed_deliveries
+    }
+
+    fn notify(&mut self, event: WatchEvent) {
+        let ids: Vec<usize> = self.subscribers.iter().map(|(id, _)| *id).collect();
+        for id in ids {
+            self.enqueue_for(id, event.clone());
+        }
+    }
+
+    /// Hand `event` to subscriber `id`, respecting `buffer.max_in_flight`.
+    /// Identical pending events are coalesced rather than queued twice,
+    /// since `WatchEvent` derives `PartialEq`.
+    fn enqueue_for(&mut self, id: usize, event: WatchEvent) {
+        if self.pending.get(&id) == Some(&event) {
+            return;
+        }
+
+        let in_flight = *self.in_flight.get(&id).unwrap_or(&0);
+        if in_flight < self.buffer.max_in_flight {
+            self.dispatch(id, event);
+            return;
+        }
+
+        match self.buffer.overflow {
+            OverflowPolicy::DropNewest => {
+                self.pending.entry(id).or_insert(event);
+            }
+            OverflowPolicy::DropOldest | OverflowPolicy::Block => {
+                self.pending.insert(id, event);
+            }
+        }
+    }
+
+    fn dispatch(&mut self, id: usize, event: WatchEvent) {
+        if let Some((_, recipient)) = self.subscribers.iter().find(|(sid, _)| *sid == id) {
+            *self.in_flight.entry(id).or_insert(0) += 1;
+            self.notifications
+                .push(track_delivery(id, recipient.send(event)));
+        }
+    }
+
+    /// Under `OverflowPolicy::Block`, the caller stops making progress while
+    /// any subscriber still has a notification backed up.
+    fn is_blocked(&self) -> bool {
+        self.buffer.overflow == OverflowPolicy::Block && !self.pending.is_empty()
+    }
+
+    /// Drive the in-flight deliveries to completion. Because `notifications`
+    /// is a `FuturesUnordered`, a slow or full subscriber mailbox no longer
+    /// blocks delivery to the rest of the set: each ready future is drained
+    /// as soon as it resolves, not in enqueue order.
+    fn poll_notifications(&mut self) -> Poll<(), ()> {
+        loop {
+            match self.notifications.poll() {
+                Ok(Async::Ready(Some((id, delivered)))) => {
+                    if let Some(count) = self.in_flight.get_mut(&id) {
+                        *count = count.saturating_sub(1);
+                    }
+
+                    if !delivered {
+                        self.failed_deliveries += 1;
+                        warn!(
+                            "NotificationHub: dropping subscriber {} after failed delivery",
+                            id
+                        );
+                        self.subscribers.retain(|(sid, _)| *sid != id);
+                        self.in_flight.remove(&id);
+                        self.pending.remove(&id);
+                        continue;
+                    }
+
+                    if let Some(next) = self.pending.remove(&id) {
+                        self.dispatch(id, next);
+                    }
+                }
+                Ok(Async::Ready(None)) => return Ok(Async::Ready(())),
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(_) => unreachable!("Delivery futures never resolve to an error"),
+            }
+        }
+    }
+}
+
 pub struct Watcher<A>
 where
     A: Actor<Context = Context<A>>,
 {
     fut: Option<ContextFut<A, Context<A>>>,
-    subscribers: Vec<Recipient<WatchEvent>>,
-    notifications: Vec<RecipientRequest<WatchEvent>>,
+    factory: Box<dyn FnMut(&mut Context<A>) -> A>,
+    receiver: channel::AddressReceiver<A>,
+    hub: NotificationHub,
+    state: WatchSender,
+    restart_policy: RestartPolicy,
+    backoff: Backoff,
+    attempt: usize,
+    stopped_with_error: bool,
+    restart_pending: bool,
 }
 
-impl <A> Watcher<A>
+impl<A> Watcher<A>
 where
     A: Actor<Context = Context<A>>,
 {
-    pub fn new(r: Recipient<WatchEvent>, f: ContextFut<A, Context<A>>) -> Watcher<A>
+    pub fn new<F>(
+        r: Recipient<WatchEvent>,
+        receiver: channel::AddressReceiver<A>,
+        mut factory: F,
+        restart_policy: RestartPolicy,
+        backoff: Backoff,
+        buffer: BufferPolicy,
+    ) -> Watcher<A>
     where
-        A: Actor<Context = Context<A>>,
+        F: FnMut(&mut Context<A>) -> A + 'static,
     {
+        let (state, _) = WatchHandle::channel(WatchEvent::Starting);
+
+        let mut ctx = Context::with_receiver(receiver.clone());
+        let act = factory(&mut ctx);
+        let fut = ctx.into_future(act);
+
+        state.set(WatchEvent::Running);
+
+        let mut hub = NotificationHub::new(buffer);
+        hub.subscribe(r);
+
         Watcher {
-            fut: Some(f),
-            subscribers: vec![r],
-            notifications: vec![],
+            fut: Some(fut),
+            factory: Box::new(factory),
+            receiver,
+            hub,
+            state,
+            restart_policy,
+            backoff,
+            attempt: 0,
+            stopped_with_error: false,
+            restart_pending: false,
         }
     }
 
-    pub fn start<F>(r: Recipient<WatchEvent>, f: F) -> Addr<A>
+    pub fn start<F>(r: Recipient<WatchEvent>, f: F) -> (Addr<A>, Addr<Watcher<A>>, WatchHandle)
     where
-        F: FnOnce(&mut A::Context) -> A + 'static,
-        A: Actor<Context = Context<A>>,
+        F: FnMut(&mut A::Context) -> A + 'static,
     {
-        // create actor
-        let mut ctx = Context::new();
-        let act = f(&mut ctx);
-        let addr = ctx.address();
-        let fut = ctx.into_future(act);
+        Watcher::start_supervised(
+            r,
+            f,
+            RestartPolicy::Never,
+            Backoff::default(),
+            BufferPolicy::default(),
+        )
+    }
 
-        // create watcher
-        Arbiter::spawn(Watcher::new(r, fut));
+    /// Start a new supervised actor, restarting it according to `restart_policy`
+    /// whenever it stops, with `backoff` applied between attempts and `buffer`
+    /// capping how many notifications may be in flight per subscriber. Returns
+    /// the watched actor's address, the supervisor's own address (for dynamic
+    /// `Subscribe`/`Unsubscribe`), and a `WatchHandle` onto its current
+    /// lifecycle state.
+    pub fn start_supervised<F>(
+        r: Recipient<WatchEvent>,
+        f: F,
+        restart_policy: RestartPolicy,
+        backoff: Backoff,
+        buffer: BufferPolicy,
+    ) -> (Addr<A>, Addr<Watcher<A>>, WatchHandle)
+    where
+        F: FnMut(&mut A::Context) -> A + 'static,
+    {
+        let (tx, rx) = channel::channel(DEFAULT_CAPACITY);
+        let addr = Addr::new(tx);
 
-        addr
+        let watcher = Watcher::new(r, rx, f, restart_policy, backoff, buffer);
+        let handle = watcher.state.subscribe();
+        let watcher_addr = watcher.start();
+
+        (addr, watcher_addr, handle)
     }
 
     /// Start new supervised actor in arbiter's thread.
     pub fn start_in_arbiter<F>(sys: &Addr<Arbiter>, r: Recipient<WatchEvent>, f: F) -> Addr<A>
     where
         A: Actor<Context = Context<A>>,
-        F: FnOnce(&mut Context<A>) -> A + Send + 'static,
+        F: FnMut(&mut Context<A>) -> A + Send + 'static,
     {
         let (tx, rx) = channel::channel(DEFAULT_CAPACITY);
 
         sys.do_send(Execute::new(move || -> Result<(), ()> {
-            let mut ctx = Context::with_receiver(rx);
-            let act = f(&mut ctx);
-            let fut = ctx.into_future(act);
-
-            Arbiter::spawn(Watcher::new(r, fut));
+            Watcher::new(
+                r,
+                rx,
+                f,
+                RestartPolicy::Never,
+                Backoff::default(),
+                BufferPolicy::default(),
+            )
+            .start();
             Ok(())
         }));
 
         Addr::new(tx)
     }
 
+    /// Number of subscriber deliveries that have failed (and so been pruned)
+    /// over the lifetime of this supervisor.
+    pub fn failed_deliveries(&self) -> usize {
+        self.hub.failed_deliveries()
+    }
+
     fn notify(&mut self, event: WatchEvent) {
-        for s in self.subscribers.iter() {
-            self.notifications.push(s.send(event.clone()));
-        }
+        self.state.set(event.clone());
+        self.hub.notify(event);
     }
 
-    fn poll_for_event(&mut self) -> Option<WatchEvent> {
-        if let Some(ref mut fut) = self.fut {
-            match fut.poll() {
-                Ok(Async::NotReady) => None,
-                Ok(Async::Ready(_)) | Err(_) => {
-                    Some(WatchEvent::Stopped)
+    /// Under `OverflowPolicy::Block`, the supervisor holds back a pending
+    /// restart while any subscriber still has a notification backed up.
+    fn is_blocked(&self) -> bool {
+        self.hub.is_blocked()
+    }
+
+    /// Spawn the watched actor's future onto this supervisor's own context so
+    /// it is driven reactively by its own waker, rather than re-polled on a
+    /// fixed interval: completion (clean or errored) is only observed once
+    /// the watched actor actually stops.
+    fn drive_fut(&mut self, ctx: &mut Context<Self>) {
+        if let Some(watched) = self.fut.take() {
+            let driven = watched.into_actor(self).then(|res, act, ctx| {
+                act.stopped_with_error = res.is_err();
+                act.state.set(WatchEvent::Stopping);
+                act.notify(WatchEvent::Stopped);
+
+                if act.should_restart() {
+                    act.restart_pending = true;
+                    act.maybe_restart(ctx);
                 }
-            }
-        } else {
-            None
+
+                fut::ok(())
+            });
+            ctx.spawn(driven);
         }
     }
 
-    fn poll_notifications(&mut self) -> Poll<(), ()> {
-        let mut notifications = self.notifications.split_off(0);
-
-        while let Some(mut n) = notifications.pop() {
-            match n.poll() {
-                Ok(Async::NotReady) => {
-                    self.notifications.push(n);
-                },
-                Ok(Async::Ready(_)) | Err(_) => {
-                }
-            }
+    fn should_restart(&self) -> bool {
+        match self.restart_policy {
+            RestartPolicy::Never => false,
+            RestartPolicy::Always => true,
+            RestartPolicy::OnPanic => self.stopped_with_error,
+            RestartPolicy::UpTo(n) => self.attempt < n,
         }
+    }
 
-        if self.notifications.is_empty() {
-            Ok(Async::Ready(()))
-        } else {
-            Ok(Async::NotReady)
+    /// Retry a restart deferred by `OverflowPolicy::Block`: under that policy
+    /// a pending restart is not scheduled until every subscriber's backlog
+    /// has drained. The watched actor is not paused.
+    fn maybe_restart(&mut self, ctx: &mut Context<Self>) {
+        if !self.restart_pending || self.is_blocked() {
+            return;
         }
+
+        self.restart_pending = false;
+        self.schedule_restart(ctx);
+    }
+
+    /// Queue the next restart attempt behind the configured backoff delay.
+    fn schedule_restart(&mut self, ctx: &mut Context<Self>) {
+        self.attempt += 1;
+        self.notify(WatchEvent::Restarting {
+            attempt: self.attempt,
+        });
+
+        let delay = self.backoff.delay_for(self.attempt - 1);
+        ctx.run_later(delay, |act, ctx| act.restart(ctx));
+    }
+
+    /// Rebuild the actor against the reused receiver, so the `Addr` handed out
+    /// by `start` stays valid across restarts.
+    fn restart(&mut self, ctx: &mut Context<Self>) {
+        self.state.set(WatchEvent::Starting);
+
+        let mut actor_ctx = Context::with_receiver(self.receiver.clone());
+        let act = (self.factory)(&mut actor_ctx);
+        self.fut = Some(actor_ctx.into_future(act));
+
+        self.notify(WatchEvent::Restarted);
+        self.state.set(WatchEvent::Running);
+
+        self.drive_fut(ctx);
+    }
+
+    fn poll_notifications(&mut self) -> Poll<(), ()> {
+        self.hub.poll_notifications()
+    }
+}
+
+impl<A> Actor for Watcher<A>
+where
+    A: Actor<Context = Context<A>>,
+{
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.drive_fut(ctx);
+        ctx.spawn(NotificationDrain(PhantomData));
+    }
+}
+
+/// Perpetual spawned task that keeps `notifications` draining as deliveries
+/// complete. Never resolves: each poll re-registers wakers for whatever is
+/// still outstanding via `FuturesUnordered`, so new notifications pushed in
+/// after this has parked are still picked up the next time this supervisor's
+/// context wakes (e.g. to handle the push itself).
+struct NotificationDrain<A>(PhantomData<A>);
+
+impl<A> ActorFuture for NotificationDrain<A>
+where
+    A: Actor<Context = Context<A>>,
+{
+    type Item = ();
+    type Error = ();
+    type Actor = Watcher<A>;
+
+    fn poll(&mut self, act: &mut Watcher<A>, ctx: &mut Context<Watcher<A>>) -> Poll<(), ()> {
+        let _ = act.poll_notifications();
+        act.maybe_restart(ctx);
+        Ok(Async::NotReady)
+    }
+}
+
+impl<A> Handler<Subscribe> for Watcher<A>
+where
+    A: Actor<Context = Context<A>>,
+{
+    type Result = usize;
+
+    fn handle(&mut self, msg: Subscribe, _ctx: &mut Context<Self>) -> usize {
+        self.hub.subscribe(msg.0)
     }
 }
 
-#[doc(hidden)]
-impl<A> Future for Watcher<A>
+impl<A> Handler<Unsubscribe> for Watcher<A>
 where
     A: Actor<Context = Context<A>>,
+{
+    type Result = ();
+
+    fn handle(&mut self, msg: Unsubscribe, _ctx: &mut Context<Self>) {
+        self.hub.unsubscribe(msg.0)
+    }
+}
+
+/// Generic stream-driven counterpart to `Watcher`: pumps items from an
+/// arbitrary `Stream` out to subscriber recipients instead of observing a
+/// `ContextFut`'s completion. Shares `NotificationHub` with `Watcher`, so the
+/// same subscriber ids, `BufferPolicy` capping/coalescing, and dead-recipient
+/// pruning apply here too, rather than a second bespoke (and unbounded)
+/// notification path.
+struct StreamWatcher<S, E>
+where
+    S: Stream<Item = E>,
+    E: Into<WatchEvent>,
+{
+    stream: Option<S>,
+    hub: NotificationHub,
+}
+
+impl<S, E> StreamWatcher<S, E>
+where
+    S: Stream<Item = E> + 'static,
+    E: Into<WatchEvent> + 'static,
+{
+    fn new(
+        recipients: Vec<Recipient<WatchEvent>>,
+        stream: S,
+        buffer: BufferPolicy,
+    ) -> StreamWatcher<S, E> {
+        let mut hub = NotificationHub::new(buffer);
+        for recipient in recipients {
+            hub.subscribe(recipient);
+        }
+
+        StreamWatcher {
+            stream: Some(stream),
+            hub,
+        }
+    }
+}
+
+impl<S, E> Future for StreamWatcher<S, E>
+where
+    S: Stream<Item = E> + 'static,
+    E: Into<WatchEvent> + 'static,
 {
     type Item = ();
     type Error = ();
 
-    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        if let Some(e) = self.poll_for_event() {
-            self.notify(e);
+    fn poll(&mut self) -> Poll<(), ()> {
+        loop {
+            // Under `OverflowPolicy::Block`, stop pulling more items out of
+            // the stream until every subscriber's backlog has drained, the
+            // same way `Watcher` pauses restarts while blocked.
+            if self.hub.is_blocked() {
+                break;
+            }
 
-            self.fut = None;
+            let polled = match self.stream {
+                Some(ref mut stream) => stream.poll().ok(),
+                None => break,
+            };
+
+            match polled {
+                Some(Async::Ready(Some(item))) => self.hub.notify(item.into()),
+                Some(Async::Ready(None)) | None => {
+                    self.stream = None;
+                    self.hub.notify(WatchEvent::Stopped);
+                }
+                Some(Async::NotReady) => break,
+            }
         }
 
-        if self.fut.is_none() {
-            self.poll_notifications()
-        } else {
+        // An empty delivery set only means nothing is in flight right now;
+        // keep running until the stream itself has ended.
+        match self.hub.poll_notifications() {
+            Ok(Async::Ready(())) if self.stream.is_some() => Ok(Async::NotReady),
+            drained => drained,
+        }
+    }
+}
+
+/// Bridge an arbitrary stream of domain events into the subscriber machinery
+/// `Watcher` uses: each item is converted into a `WatchEvent` and fanned out
+/// to `recipients`, and stream termination is reported the same way a
+/// supervised actor's completion is today, as `WatchEvent::Stopped`. Uses a
+/// default `BufferPolicy`; see `watch_stream_with_buffer` to pick one.
+pub fn watch_stream<S, E>(recipients: Vec<Recipient<WatchEvent>>, stream: S)
+where
+    S: Stream<Item = E> + 'static,
+    E: Message<Result = ()> + Clone + Into<WatchEvent> + 'static,
+{
+    watch_stream_with_buffer(recipients, stream, BufferPolicy::default());
+}
+
+/// Like `watch_stream`, but with an explicit `BufferPolicy` capping how many
+/// deliveries may be in flight per subscriber and what happens on overflow.
+pub fn watch_stream_with_buffer<S, E>(
+    recipients: Vec<Recipient<WatchEvent>>,
+    stream: S,
+    buffer: BufferPolicy,
+) where
+    S: Stream<Item = E> + 'static,
+    E: Message<Result = ()> + Clone + Into<WatchEvent> + 'static,
+{
+    Arbiter::spawn(StreamWatcher::new(recipients, stream, buffer));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicBool;
+
+    use futures::executor::{self, Notify, NotifyHandle};
+
+    struct Flag(AtomicBool);
+
+    impl Notify for Flag {
+        fn notify(&self, _id: usize) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn borrow_reflects_transitions_that_happened_before_subscribing() {
+        let (sender, _initial) = WatchHandle::channel(WatchEvent::Starting);
+        sender.set(WatchEvent::Running);
+        sender.set(WatchEvent::Stopping);
+
+        let handle = sender.subscribe();
+
+        assert_eq!(handle.borrow(), WatchEvent::Stopping);
+    }
+
+    #[test]
+    fn every_clone_is_woken_on_each_transition() {
+        let (sender, handle_a) = WatchHandle::channel(WatchEvent::Starting);
+        let handle_b = handle_a.clone();
+
+        let flag_a = Arc::new(Flag(AtomicBool::new(false)));
+        let flag_b = Arc::new(Flag(AtomicBool::new(false)));
+
+        let mut spawn_a = executor::spawn(handle_a);
+        let mut spawn_b = executor::spawn(handle_b);
+
+        assert_eq!(
+            spawn_a.poll_future_notify(&NotifyHandle::from(flag_a.clone()), 0),
             Ok(Async::NotReady)
+        );
+        assert_eq!(
+            spawn_b.poll_future_notify(&NotifyHandle::from(flag_b.clone()), 0),
+            Ok(Async::NotReady)
+        );
+
+        sender.set(WatchEvent::Running);
+
+        assert!(flag_a.0.load(Ordering::SeqCst));
+        assert!(flag_b.0.load(Ordering::SeqCst));
+    }
+
+    struct TestActor;
+
+    impl Actor for TestActor {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<WatchEvent> for TestActor {
+        type Result = ();
+
+        fn handle(&mut self, _msg: WatchEvent, _ctx: &mut Context<Self>) {}
+    }
+
+    fn subscriber() -> Recipient<WatchEvent> {
+        let (tx, _rx) = channel::channel::<TestActor>(DEFAULT_CAPACITY);
+        Addr::new(tx).recipient()
+    }
+
+    /// A hub with a single subscriber, just enough state to exercise the
+    /// per-subscriber buffering logic in isolation.
+    fn blank_hub(buffer: BufferPolicy) -> NotificationHub {
+        let mut hub = NotificationHub::new(buffer);
+        hub.subscribe(subscriber());
+        hub
+    }
+
+    #[test]
+    fn drop_oldest_replaces_backlog_with_latest_event() {
+        let mut hub = blank_hub(BufferPolicy::new(1, OverflowPolicy::DropOldest));
+        hub.in_flight.insert(0, 1);
+
+        hub.enqueue_for(0, WatchEvent::Running);
+        hub.enqueue_for(0, WatchEvent::Stopping);
+
+        assert_eq!(hub.pending.get(&0), Some(&WatchEvent::Stopping));
+        assert!(!hub.is_blocked());
+    }
+
+    #[test]
+    fn drop_newest_keeps_first_queued_event() {
+        let mut hub = blank_hub(BufferPolicy::new(1, OverflowPolicy::DropNewest));
+        hub.in_flight.insert(0, 1);
+
+        hub.enqueue_for(0, WatchEvent::Running);
+        hub.enqueue_for(0, WatchEvent::Stopping);
+
+        assert_eq!(hub.pending.get(&0), Some(&WatchEvent::Running));
+        assert!(!hub.is_blocked());
+    }
+
+    #[test]
+    fn block_reports_backpressure_until_backlog_drains() {
+        let mut hub = blank_hub(BufferPolicy::new(1, OverflowPolicy::Block));
+        hub.in_flight.insert(0, 1);
+
+        assert!(!hub.is_blocked());
+
+        hub.enqueue_for(0, WatchEvent::Running);
+        assert!(hub.is_blocked());
+
+        hub.pending.remove(&0);
+        assert!(!hub.is_blocked());
+    }
+
+    #[test]
+    fn closed_mailbox_is_pruned_from_subscribers() {
+        let mut hub = NotificationHub::new(BufferPolicy::default());
+        let (tx, rx) = channel::channel::<TestActor>(DEFAULT_CAPACITY);
+        let id = hub.subscribe(Addr::new(tx).recipient());
+        drop(rx);
+
+        hub.notify(WatchEvent::Running);
+        {
+            // `FuturesUnordered` registers the current task when polled, so
+            // the drain has to run inside one.
+            let flag = Arc::new(Flag(AtomicBool::new(false)));
+            let mut drain = executor::spawn(::futures::future::poll_fn(|| hub.poll_notifications()));
+            while let Ok(Async::NotReady) =
+                drain.poll_future_notify(&NotifyHandle::from(flag.clone()), 0)
+            {}
         }
+
+        assert!(hub.subscribers.iter().all(|(sid, _)| *sid != id));
+        assert_eq!(hub.failed_deliveries(), 1);
     }
-}
\ No newline at end of file
+
+    struct CountingActor(Arc<AtomicUsize>);
+
+    impl Actor for CountingActor {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<WatchEvent> for CountingActor {
+        type Result = ();
+
+        fn handle(&mut self, _msg: WatchEvent, _ctx: &mut Context<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn addr_stays_valid_after_receiver_is_reused_across_a_restart() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = channel::channel::<CountingActor>(DEFAULT_CAPACITY);
+        let addr = Addr::new(tx);
+
+        // Mirrors `Watcher::restart`: a fresh `Context` built from a clone of
+        // the receiver the original `Addr` still sends into.
+        let mut ctx = Context::with_receiver(rx.clone());
+        let mut incarnation = ctx.into_future(CountingActor(count.clone()));
+        drop(rx);
+
+        addr.do_send(WatchEvent::Running);
+
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let mut spawn = executor::spawn(&mut incarnation);
+        let _ = spawn.poll_future_notify(&NotifyHandle::from(flag), 0);
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    /// A `Watcher` with no actor future attached, just enough state to
+    /// exercise `should_restart`'s policy logic in isolation.
+    fn blank_watcher(restart_policy: RestartPolicy) -> Watcher<TestActor> {
+        let (_tx, rx) = channel::channel::<TestActor>(DEFAULT_CAPACITY);
+
+        Watcher {
+            fut: None,
+            factory: Box::new(|_ctx: &mut Context<TestActor>| TestActor),
+            receiver: rx,
+            hub: blank_hub(BufferPolicy::default()),
+            state: WatchHandle::channel(WatchEvent::Starting).0,
+            restart_policy,
+            backoff: Backoff::default(),
+            attempt: 0,
+            stopped_with_error: false,
+            restart_pending: false,
+        }
+    }
+
+    #[test]
+    fn on_panic_only_restarts_after_an_error() {
+        let mut w = blank_watcher(RestartPolicy::OnPanic);
+        assert!(!w.should_restart());
+
+        w.stopped_with_error = true;
+        assert!(w.should_restart());
+    }
+
+    #[test]
+    fn up_to_stops_restarting_once_the_limit_is_reached() {
+        let mut w = blank_watcher(RestartPolicy::UpTo(2));
+        assert!(w.should_restart());
+
+        w.attempt = 2;
+        assert!(!w.should_restart());
+    }
+}